@@ -0,0 +1,15 @@
+use crate::physics::body::Body;
+use nalgebra::Vector2;
+use super::force_calculator::BarnesHutForceCalculator;
+
+/// Common interface for spatial indices that approximate n-body forces with the
+/// Barnes-Hut opening-angle criterion, so callers can swap accelerators (quadtree,
+/// KD-tree, ...) without changing the simulation loop around them.
+pub trait ForceAccelerator {
+    /// (Re)builds the index from scratch for the current body positions.
+    fn build(&mut self, bodies: &[Body]);
+
+    /// Computes the net force on every body, approximating distant clusters via
+    /// `calc`'s opening angle, mass softening, and gravitational constant.
+    fn calculate_forces(&self, bodies: &[Body], calc: &BarnesHutForceCalculator) -> Vec<Vector2<f64>>;
+}
@@ -2,6 +2,7 @@ use nalgebra::{Point2, Vector2};
 use crate::physics::body::Body;
 use super::quadtree::{Quadtree, QuadNode};
 use rayon::prelude::*;
+use wide::f64x4;
 
 pub struct BarnesHutForceCalculator {
     pub theta: f64,
@@ -30,13 +31,7 @@ impl BarnesHutForceCalculator {
     ) -> Vector2<f64> {
         match node {
             QuadNode::Leaf { body_indices } => {
-                let mut force = Vector2::new(0.0, 0.0);
-                for &other_index in body_indices {
-                    if other_index != body_index {
-                        force += self.calculate_direct_force(body_index, other_index, bodies);
-                    }
-                }
-                force
+                self.calculate_direct_force_simd(body_index, body_indices, bodies)
             }
             QuadNode::Internal { children, center_of_mass, total_mass } => {
                 if *total_mass == 0.0 {
@@ -61,7 +56,7 @@ impl BarnesHutForceCalculator {
         }
     }
 
-    fn should_use_approximation(
+    pub(crate) fn should_use_approximation(
         &self,
         body_pos: Point2<f64>,
         node_center: Point2<f64>,
@@ -71,22 +66,81 @@ impl BarnesHutForceCalculator {
         distance > node_width / self.theta && distance > 1e-10
     }
 
-    fn calculate_direct_force(&self, body1_index: usize, body2_index: usize, bodies: &[Body]) -> Vector2<f64> {
-        let body1 = &bodies[body1_index];
-        let body2 = &bodies[body2_index];
-        
-        let r = body2.pos() - body1.pos();
-        let r_squared = r.magnitude_squared();
-        
-        if r_squared < self.eps * self.eps {
-            return Vector2::new(0.0, 0.0);
+    /// Softened pairwise force from a single source body: both direction and
+    /// magnitude are computed against the same `r² + eps` (a Plummer-style softening),
+    /// so this is the one force law every accelerator and every branch of a tree
+    /// traversal uses — a body crossing the Barnes-Hut opening-angle boundary sees a
+    /// change in approximation quality, not a discontinuous jump to a different law.
+    pub(crate) fn softened_pair_force(&self, mass_i: f64, mass_j: f64, dx: f64, dy: f64) -> Vector2<f64> {
+        let r_squared = dx * dx + dy * dy + self.eps;
+        let inv_r = 1.0 / r_squared.sqrt();
+        let force_magnitude = self.g * mass_i * mass_j * inv_r / r_squared;
+        Vector2::new(dx * force_magnitude, dy * force_magnitude)
+    }
+
+    /// Same pairwise force sum as looping `softened_pair_force` over `other_indices`,
+    /// but processes four source bodies per `f64x4` lane to amortize the per-pair sqrt.
+    /// `body_index` itself may appear in `other_indices` (as it does for quadtree leaves);
+    /// its lane is zeroed out rather than skipped, so it contributes nothing.
+    pub(crate) fn calculate_direct_force_simd(
+        &self,
+        body_index: usize,
+        other_indices: &[usize],
+        bodies: &[Body],
+    ) -> Vector2<f64> {
+        let body = &bodies[body_index];
+        let px = body.pos().x;
+        let py = body.pos().y;
+        let mi = body.mass();
+
+        let mut total = Vector2::new(0.0, 0.0);
+        let chunks = other_indices.chunks_exact(4);
+        let remainder = chunks.remainder();
+
+        for chunk in chunks {
+            let mut dx = [0.0; 4];
+            let mut dy = [0.0; 4];
+            let mut mj = [0.0; 4];
+
+            for (lane, &index) in chunk.iter().enumerate() {
+                if index == body_index {
+                    continue;
+                }
+                let other = &bodies[index];
+                dx[lane] = other.pos().x - px;
+                dy[lane] = other.pos().y - py;
+                mj[lane] = other.mass();
+            }
+
+            let dx = f64x4::from(dx);
+            let dy = f64x4::from(dy);
+            let mj = f64x4::from(mj);
+
+            let r2 = dx * dx + dy * dy + f64x4::splat(self.eps);
+            let inv_r = f64x4::splat(1.0) / r2.sqrt();
+            let f = f64x4::splat(self.g * mi) * mj * inv_r / r2;
+
+            let fx: [f64; 4] = (dx * f).into();
+            let fy: [f64; 4] = (dy * f).into();
+            total += Vector2::new(fx.iter().sum(), fy.iter().sum());
         }
-        
-        let force_magnitude = self.g * body1.mass() * body2.mass() / (r_squared + self.eps);
-        r.normalize() * force_magnitude
+
+        for &index in remainder {
+            if index != body_index {
+                let other = &bodies[index];
+                total += self.softened_pair_force(
+                    mi,
+                    other.mass(),
+                    other.pos().x - px,
+                    other.pos().y - py,
+                );
+            }
+        }
+
+        total
     }
 
-    fn calculate_force_from_center_of_mass(
+    pub(crate) fn calculate_force_from_center_of_mass(
         &self,
         body_index: usize,
         bodies: &[Body],
@@ -95,21 +149,14 @@ impl BarnesHutForceCalculator {
     ) -> Vector2<f64> {
         let body = &bodies[body_index];
         let r = center_of_mass - body.pos();
-        let r_squared = r.magnitude_squared();
-        
-        if r_squared < self.eps * self.eps {
-            return Vector2::new(0.0, 0.0);
-        }
-        
-        let force_magnitude = self.g * body.mass() * total_mass / (r_squared + self.eps);
-        r.normalize() * force_magnitude
+        self.softened_pair_force(body.mass(), total_mass, r.x, r.y)
     }
 
     fn get_child_bounds(parent_bounds: (f64, f64, f64, f64), quadrant: usize) -> (f64, f64, f64, f64) {
         let (min_x, max_x, min_y, max_y) = parent_bounds;
         let mid_x = (min_x + max_x) / 2.0;
         let mid_y = (min_y + max_y) / 2.0;
-        
+
         match quadrant {
             0 => (min_x, mid_x, mid_y, max_y),
             1 => (mid_x, max_x, mid_y, max_y),
@@ -118,4 +165,54 @@ impl BarnesHutForceCalculator {
             _ => panic!("Invalid quadrant: {}", quadrant),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Brute-force reference for `softened_pair_force`'s law, written independently
+    /// of the SIMD kernel so a regression in either the scalar remainder loop or the
+    /// `f64x4` lanes shows up as a mismatch rather than both sides drifting together.
+    fn reference_force(calc: &BarnesHutForceCalculator, body_index: usize, other_indices: &[usize], bodies: &[Body]) -> Vector2<f64> {
+        let body = &bodies[body_index];
+        let mut total = Vector2::new(0.0, 0.0);
+        for &index in other_indices {
+            if index == body_index {
+                continue;
+            }
+            let other = &bodies[index];
+            let r = other.pos() - body.pos();
+            let r_squared = r.magnitude_squared() + calc.eps;
+            let force_magnitude = calc.g * body.mass() * other.mass() / (r_squared.sqrt() * r_squared);
+            total += r * force_magnitude;
+        }
+        total
+    }
+
+    #[test]
+    fn simd_kernel_matches_brute_force_reference() {
+        let calc = BarnesHutForceCalculator::new(0.5, 100.0, 5e-3);
+        let bodies = vec![
+            Body::new(0.0, 0.0, 0.0, 0.0, 10.0),
+            Body::new(1.0, 0.0, 0.0, 0.0, 2.0),
+            Body::new(0.0, 2.0, 0.0, 0.0, 3.0),
+            Body::new(-1.5, -1.0, 0.0, 0.0, 1.0),
+            Body::new(3.0, 4.0, 0.0, 0.0, 5.0),
+            Body::new(-2.0, 3.0, 0.0, 0.0, 4.0),
+            Body::new(0.1, 0.1, 0.0, 0.0, 1.0),
+        ];
+
+        // 7 indices split into one full 4-lane SIMD chunk (0..=3) and a 3-element
+        // scalar remainder (4..=6): body 0 is zeroed inside a SIMD lane, body 6 is
+        // skipped inside the remainder loop.
+        let all_indices: Vec<usize> = (0..bodies.len()).collect();
+        for body_index in [0usize, 6usize] {
+            let simd = calc.calculate_direct_force_simd(body_index, &all_indices, &bodies);
+            let reference = reference_force(&calc, body_index, &all_indices, &bodies);
+            let diff = (simd - reference).magnitude();
+            let scale = reference.magnitude().max(1e-12);
+            assert!(diff / scale < 1e-9, "simd={simd:?} reference={reference:?}");
+        }
+    }
 }
\ No newline at end of file
@@ -1,4 +1,10 @@
 pub mod quadtree;
 pub mod force_calculator;
+pub mod accelerator;
+pub mod kdtree;
+pub mod rect;
 pub use quadtree::Quadtree;
 pub use force_calculator::BarnesHutForceCalculator;
+pub use accelerator::ForceAccelerator;
+pub use kdtree::KdTree;
+pub use rect::RectF64;
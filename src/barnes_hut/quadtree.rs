@@ -1,5 +1,8 @@
 use crate::physics::body::Body;
 use nalgebra::{Point2, Vector2};
+use super::accelerator::ForceAccelerator;
+use super::force_calculator::BarnesHutForceCalculator;
+use super::rect::RectF64;
 
 #[derive(Debug, Clone)]
 pub enum QuadNode {
@@ -24,6 +27,10 @@ impl QuadNode {
     }
 }
 
+/// Extra margin added around the tightest enclosing square, so bodies sitting
+/// exactly on the boundary still fall cleanly inside a quadrant.
+const BOUNDS_PADDING_FRACTION: f64 = 0.05;
+
 pub struct Quadtree {
     root: QuadNode,
     bounds: (f64, f64, f64, f64),
@@ -38,7 +45,59 @@ impl Quadtree {
             max_bodies_per_leaf,
         }
     }
-    
+
+    /// Builds a tree whose bounds are fitted to `bodies` instead of a caller-supplied
+    /// rectangle, so bodies that have drifted off whatever viewport the caller had in
+    /// mind still land in the correct quadrant. See `fit_bounds` for how the square is
+    /// derived.
+    pub fn build_auto(bodies: &[Body], max_bodies_per_leaf: usize) -> Self {
+        let mut tree = Self::new(Self::fit_bounds(bodies), max_bodies_per_leaf);
+        tree.build_from_bodies(bodies);
+        tree
+    }
+
+    /// Computes a square, padded, axis-aligned box enclosing every body. Barnes-Hut's
+    /// opening-angle test assumes roughly square cells, so the tightest enclosing
+    /// rectangle is expanded on its shorter axis to match the longer one.
+    fn fit_bounds(bodies: &[Body]) -> (f64, f64, f64, f64) {
+        if bodies.is_empty() {
+            return (-0.5, 0.5, -0.5, 0.5);
+        }
+
+        let mut min_x = f64::INFINITY;
+        let mut max_x = f64::NEG_INFINITY;
+        let mut min_y = f64::INFINITY;
+        let mut max_y = f64::NEG_INFINITY;
+
+        for body in bodies {
+            let pos = body.pos();
+            min_x = min_x.min(pos.x);
+            max_x = max_x.max(pos.x);
+            min_y = min_y.min(pos.y);
+            max_y = max_y.max(pos.y);
+        }
+
+        let width = max_x - min_x;
+        let height = max_y - min_y;
+
+        // All bodies coincide (including the single-body case): fall back to a unit
+        // box centered on them so `subdivide_node`'s `width < 1.0` guard still terminates.
+        if width <= 0.0 && height <= 0.0 {
+            return (min_x - 0.5, min_x + 0.5, min_y - 0.5, min_y + 0.5);
+        }
+
+        let center_x = (min_x + max_x) / 2.0;
+        let center_y = (min_y + max_y) / 2.0;
+        let half_extent = (width.max(height) / 2.0) * (1.0 + BOUNDS_PADDING_FRACTION);
+
+        (
+            center_x - half_extent,
+            center_x + half_extent,
+            center_y - half_extent,
+            center_y + half_extent,
+        )
+    }
+
     pub fn insert_body(&mut self, body_index: usize, bodies: &[Body]) {
         let bounds = self.bounds;
         let max_bodies = self.max_bodies_per_leaf;
@@ -179,54 +238,86 @@ impl Quadtree {
         }
     }
 
-    pub fn draw_tree(&self, node: &QuadNode, bounds: (f64, f64, f64, f64)) {
-        use macroquad::prelude::*;
-        
-        let (min_x, max_x, min_y, max_y) = bounds;
-        
-        draw_rectangle_lines(
-            min_x as f32, 
-            min_y as f32, 
-            (max_x - min_x) as f32, 
-            (max_y - min_y) as f32, 
-            1.0, 
-            GREEN
-        );
-        
-        if let QuadNode::Internal { children, .. } = node {
-            for (i, child) in children.iter().enumerate() {
-                let child_bounds = Self::get_child_bounds(bounds, i);
-                Self::draw_tree_recursive(child, child_bounds);
+    /// Returns the indices of all bodies within `radius` of `center`, pruning any
+    /// subtree whose cell doesn't intersect the query circle. Cell bounds are carried
+    /// down recursively exactly like `calculate_force_on_body` does.
+    pub fn query_radius(&self, center: Point2<f64>, radius: f64, bodies: &[Body]) -> Vec<usize> {
+        let mut found = Vec::new();
+        Self::query_radius_recursive(&self.root, self.bounds, center, radius, bodies, &mut found);
+        found
+    }
+
+    fn query_radius_recursive(
+        node: &QuadNode,
+        node_bounds: (f64, f64, f64, f64),
+        center: Point2<f64>,
+        radius: f64,
+        bodies: &[Body],
+        found: &mut Vec<usize>,
+    ) {
+        if !Self::circle_intersects_bounds(center, radius, node_bounds) {
+            return;
+        }
+
+        match node {
+            QuadNode::Leaf { body_indices } => {
+                for &index in body_indices {
+                    if (bodies[index].pos() - center).magnitude_squared() <= radius * radius {
+                        found.push(index);
+                    }
+                }
+            }
+            QuadNode::Internal { children, .. } => {
+                for (i, child) in children.iter().enumerate() {
+                    let child_bounds = Self::get_child_bounds(node_bounds, i);
+                    Self::query_radius_recursive(child, child_bounds, center, radius, bodies, found);
+                }
             }
         }
     }
 
-    fn draw_tree_recursive(node: &QuadNode, bounds: (f64, f64, f64, f64)) {
+    fn circle_intersects_bounds(center: Point2<f64>, radius: f64, bounds: (f64, f64, f64, f64)) -> bool {
+        let (min_x, max_x, min_y, max_y) = bounds;
+        let closest_x = center.x.clamp(min_x, max_x);
+        let closest_y = center.y.clamp(min_y, max_y);
+        let dx = center.x - closest_x;
+        let dy = center.y - closest_y;
+        dx * dx + dy * dy <= radius * radius
+    }
+
+    /// Draws the tree's cell outlines, pruning any subtree whose bounds don't overlap
+    /// `viewport` (min_x, max_x, min_y, max_y) before recursing, so debug-draw cost
+    /// scales with the number of visible nodes instead of the whole tree.
+    pub fn draw_visible(&self, viewport: (f64, f64, f64, f64)) {
+        let viewport_rect = RectF64::from_tuple(viewport);
+        Self::draw_tree_visible_recursive(&self.root, self.bounds, &viewport_rect);
+    }
+
+    fn draw_tree_visible_recursive(node: &QuadNode, bounds: (f64, f64, f64, f64), viewport: &RectF64) {
         use macroquad::prelude::*;
-        
+
+        if !RectF64::from_tuple(bounds).intersects(viewport) {
+            return;
+        }
+
         let (min_x, max_x, min_y, max_y) = bounds;
-        
         draw_rectangle_lines(
-            min_x as f32, 
-            min_y as f32, 
-            (max_x - min_x) as f32, 
-            (max_y - min_y) as f32, 
-            1.0, 
+            min_x as f32,
+            min_y as f32,
+            (max_x - min_x) as f32,
+            (max_y - min_y) as f32,
+            1.0,
             GREEN
         );
-        
+
         if let QuadNode::Internal { children, .. } = node {
             for (i, child) in children.iter().enumerate() {
                 let child_bounds = Self::get_child_bounds(bounds, i);
-                Self::draw_tree_recursive(child, child_bounds);
+                Self::draw_tree_visible_recursive(child, child_bounds, viewport);
             }
         }
     }
 
-    pub fn draw(&self) {
-        self.draw_tree(&self.root, self.bounds);
-    }
-
     pub fn root(&self) -> &QuadNode {
         &self.root
     }
@@ -234,4 +325,43 @@ impl Quadtree {
     pub fn bounds(&self) -> (f64, f64, f64, f64) {
         self.bounds
     }
+}
+
+impl ForceAccelerator for Quadtree {
+    fn build(&mut self, bodies: &[Body]) {
+        self.bounds = Self::fit_bounds(bodies);
+        self.build_from_bodies(bodies);
+    }
+
+    fn calculate_forces(&self, bodies: &[Body], calc: &BarnesHutForceCalculator) -> Vec<Vector2<f64>> {
+        calc.calculate_forces(bodies, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_bounds_empty_slice_falls_back_to_unit_box() {
+        let bounds = Quadtree::fit_bounds(&[]);
+        assert_eq!(bounds, (-0.5, 0.5, -0.5, 0.5));
+    }
+
+    #[test]
+    fn fit_bounds_single_body_centers_a_unit_box_on_it() {
+        let bodies = vec![Body::new(10.0, -4.0, 0.0, 0.0, 1.0)];
+        let bounds = Quadtree::fit_bounds(&bodies);
+        assert_eq!(bounds, (9.5, 10.5, -4.5, -3.5));
+    }
+
+    #[test]
+    fn fit_bounds_coincident_bodies_also_falls_back_to_unit_box() {
+        let bodies = vec![
+            Body::new(2.0, 2.0, 0.0, 0.0, 1.0),
+            Body::new(2.0, 2.0, 1.0, -1.0, 5.0),
+        ];
+        let bounds = Quadtree::fit_bounds(&bodies);
+        assert_eq!(bounds, (1.5, 2.5, 1.5, 2.5));
+    }
 }
\ No newline at end of file
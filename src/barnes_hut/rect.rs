@@ -0,0 +1,28 @@
+/// Axis-aligned rectangle, stored as (min_x, max_x, min_y, max_y) to match the tuple
+/// convention the quadtree already uses for node bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RectF64 {
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_y: f64,
+    pub max_y: f64,
+}
+
+impl RectF64 {
+    pub fn new(min_x: f64, max_x: f64, min_y: f64, max_y: f64) -> Self {
+        Self { min_x, max_x, min_y, max_y }
+    }
+
+    pub fn from_tuple(bounds: (f64, f64, f64, f64)) -> Self {
+        let (min_x, max_x, min_y, max_y) = bounds;
+        Self::new(min_x, max_x, min_y, max_y)
+    }
+
+    /// True if the two rectangles overlap, including edge contact.
+    pub fn intersects(&self, other: &RectF64) -> bool {
+        self.min_x <= other.max_x
+            && self.max_x >= other.min_x
+            && self.min_y <= other.max_y
+            && self.max_y >= other.min_y
+    }
+}
@@ -1,7 +1,9 @@
 use macroquad::prelude::*;
 use nalgebra::Vector2;
 use nbody_simulation::physics::body::Body;
-use nbody_simulation::barnes_hut::{Quadtree, BarnesHutForceCalculator};
+use nbody_simulation::physics::collision::merge_colliding_bodies;
+use nbody_simulation::physics::diagnostics::{Simulation, Diagnostics};
+use nbody_simulation::barnes_hut::{Quadtree, KdTree, BarnesHutForceCalculator, ForceAccelerator};
 use rayon::prelude::*;
 
 #[macroquad::main("N-Body Simulation")]
@@ -13,6 +15,13 @@ async fn main() {
     
     let mut use_barnes_hut = true;
     let mut show_quadtree = false;
+    let mut use_kdtree = false;
+    let mut show_diagnostics = false;
+    // Potential energy is an O(n^2) sum, too slow to recompute every frame on a
+    // 10k-body galaxy, so the diagnostics HUD only refreshes every N frames.
+    const DIAGNOSTICS_REFRESH_FRAMES: u32 = 30;
+    let mut diagnostics_timer: u32 = 0;
+    let mut cached_diagnostics: Option<Diagnostics> = None;
     let force_calculator = BarnesHutForceCalculator::new(2.0, g, eps);
     
     fn generate_normal_distribution(mean: f64, std_dev: f64) -> f64 {
@@ -52,25 +61,25 @@ async fn main() {
         let mut forces = vec![Vector2::new(0.0, 0.0); bodies.len()];
         
         if use_barnes_hut && bodies.len() > 5 {
-            let mut quadtree = Quadtree::new((0.0, screen_width() as f64, 0.0, screen_height() as f64), 1);
-            quadtree.build_from_bodies(&bodies);
-            forces = force_calculator.calculate_forces(&bodies, &quadtree);
-            if show_quadtree {
-                quadtree.draw();
-            }
-        } else {
-            for i in 0..bodies.len() {
-                for j in (i + 1)..bodies.len() {
-                    let r = bodies[j].pos() - bodies[i].pos();
-                    let r_squared = r.magnitude_squared();
-
-                    let force_magnitude = g * bodies[i].mass() * bodies[j].mass() / (r_squared + eps);
-                    let force = r.normalize() * force_magnitude;
-
-                    forces[i] += force;
-                    forces[j] -= force;
+            if use_kdtree {
+                let mut kdtree = KdTree::new();
+                kdtree.build(&bodies);
+                forces = kdtree.calculate_forces(&bodies, &force_calculator);
+            } else {
+                let mut quadtree = Quadtree::new((0.0, 0.0, 0.0, 0.0), 1);
+                quadtree.build(&bodies);
+                forces = quadtree.calculate_forces(&bodies, &force_calculator);
+                if show_quadtree {
+                    let viewport = (0.0, screen_width() as f64, 0.0, screen_height() as f64);
+                    quadtree.draw_visible(viewport);
                 }
             }
+        } else {
+            let all_indices: Vec<usize> = (0..bodies.len()).collect();
+            forces = (0..bodies.len())
+                .into_par_iter()
+                .map(|i| force_calculator.calculate_direct_force_simd(i, &all_indices, &bodies))
+                .collect();
         }
         for (body, force) in bodies.iter_mut().zip(forces.iter()) {
             body.apply_force(*force);
@@ -79,6 +88,8 @@ async fn main() {
             body.update(dt as f64);
         }
 
+        merge_colliding_bodies(&mut bodies, dt as f64);
+
         // RENDERING
         for (i, body) in bodies.iter().enumerate() {
             let x = body.x() as f32;
@@ -99,6 +110,41 @@ async fn main() {
             20.0,
             WHITE,
         );
+        if show_diagnostics {
+            if diagnostics_timer == 0 {
+                cached_diagnostics = Some(Simulation::diagnostics(&bodies, dt as f64, g, eps));
+            }
+            diagnostics_timer = (diagnostics_timer + 1) % DIAGNOSTICS_REFRESH_FRAMES;
+
+            if let Some(diagnostics) = &cached_diagnostics {
+                draw_text(
+                    &format!(
+                        "KE: {:.2e} | PE: {:.2e} | Total: {:.2e}",
+                        diagnostics.kinetic_energy,
+                        diagnostics.potential_energy,
+                        diagnostics.kinetic_energy + diagnostics.potential_energy,
+                    ),
+                    20.0,
+                    90.0,
+                    20.0,
+                    WHITE,
+                );
+                draw_text(
+                    &format!(
+                        "Momentum: ({:.2}, {:.2}) | COM: ({:.1}, {:.1})",
+                        diagnostics.momentum.x,
+                        diagnostics.momentum.y,
+                        diagnostics.center_of_mass.x,
+                        diagnostics.center_of_mass.y,
+                    ),
+                    20.0,
+                    115.0,
+                    20.0,
+                    WHITE,
+                );
+            }
+        }
+
         draw_text(
             "Click to add bodies",
             20.0,
@@ -108,9 +154,11 @@ async fn main() {
         );
         draw_text(
             &format!(
-                "[B] Barnes-Hut: {} | [Q] Show Tree: {} | [G] Generate Galaxy",
+                "[B] Barnes-Hut: {} | [Q] Show Tree: {} | [K] Accelerator: {} | [E] Diagnostics: {} | [G] Generate Galaxy",
                 if use_barnes_hut { "ON" } else { "OFF" },
-                if show_quadtree { "ON" } else { "OFF" }
+                if show_quadtree { "ON" } else { "OFF" },
+                if use_kdtree { "KD-tree" } else { "Quadtree" },
+                if show_diagnostics { "ON" } else { "OFF" }
             ),
             20.0,
             screen_height() - 30.0,
@@ -132,6 +180,14 @@ async fn main() {
             show_quadtree = !show_quadtree;
         }
 
+        if is_key_pressed(KeyCode::K) {
+            use_kdtree = !use_kdtree;
+        }
+
+        if is_key_pressed(KeyCode::E) {
+            show_diagnostics = !show_diagnostics;
+        }
+
         if is_key_pressed(KeyCode::G) {
             initialize_galaxy(&mut bodies);
         }
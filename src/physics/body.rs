@@ -49,4 +49,38 @@ impl Body {
     pub fn mass(&self) -> f64 {
         self.mass
     }
+
+    /// `vel` is only the initial velocity passed to `new` and is never updated by
+    /// position-Verlet `update`, so it goes stale after the first step. This recovers
+    /// the current velocity from the position history instead.
+    pub fn velocity(&self, dt: f64) -> Vector2<f64> {
+        (self.pos - self.prev_pos) / dt
+    }
+
+    /// Physical radius for collision purposes, assuming roughly constant density
+    /// (radius grows with the cube root of mass).
+    pub fn radius(&self) -> f64 {
+        self.mass.cbrt()
+    }
+
+    /// Merges two colliding bodies into one inelastic union: combined mass, a
+    /// mass-weighted centroid position, and momentum-conserving velocity. `prev_pos`
+    /// is set so the next position-Verlet step starts from that velocity.
+    pub fn merge(a: &Body, b: &Body, dt: f64) -> Body {
+        let total_mass = a.mass + b.mass;
+        let vel_a = a.velocity(dt);
+        let vel_b = b.velocity(dt);
+
+        let pos = Point2::from((a.pos.coords * a.mass + b.pos.coords * b.mass) / total_mass);
+        let vel = (vel_a * a.mass + vel_b * b.mass) / total_mass;
+
+        Self {
+            pos,
+            prev_pos: pos - vel * dt,
+            vel,
+            acc: Vector2::new(0.0, 0.0),
+            mass: total_mass,
+            first_step: false,
+        }
+    }
 }
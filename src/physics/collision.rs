@@ -0,0 +1,91 @@
+use crate::barnes_hut::Quadtree;
+use super::body::Body;
+
+/// Merges every pair of overlapping bodies into one, repeating until no collisions
+/// remain. The quadtree is rebuilt before each merge (rather than once up front) so
+/// that a body repositioned by an earlier merge this frame is queried against its
+/// live position instead of the stale cell it occupied when the frame started —
+/// merges are expected to be rare relative to the body count, so the extra rebuilds
+/// are cheap in practice.
+pub fn merge_colliding_bodies(bodies: &mut Vec<Body>, dt: f64) {
+    loop {
+        if bodies.len() < 2 {
+            return;
+        }
+
+        let quadtree = Quadtree::build_auto(bodies, 1);
+        let max_radius = bodies
+            .iter()
+            .map(Body::radius)
+            .fold(f64::MIN, f64::max);
+
+        let mut collision = None;
+        'search: for i in 0..bodies.len() {
+            let search_radius = bodies[i].radius() + max_radius;
+            for j in quadtree.query_radius(bodies[i].pos(), search_radius, bodies) {
+                if j == i {
+                    continue;
+                }
+
+                let collision_distance = bodies[i].radius() + bodies[j].radius();
+                let separation_squared = (bodies[j].pos() - bodies[i].pos()).magnitude_squared();
+                if separation_squared <= collision_distance * collision_distance {
+                    collision = Some((i, j));
+                    break 'search;
+                }
+            }
+        }
+
+        match collision {
+            Some((i, j)) => {
+                let merged = Body::merge(&bodies[i], &bodies[j], dt);
+                let keep = i.min(j);
+                let remove = i.max(j);
+                bodies[keep] = merged;
+                bodies.remove(remove);
+            }
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A chained collision: three unit-mass bodies (radius 1) within about 1 of each
+    /// other, so every pair overlaps. Reducing them to one body takes two successive
+    /// merges, with the tree rebuilt in between, inside a single
+    /// `merge_colliding_bodies` call.
+    #[test]
+    fn chained_collision_conserves_mass_and_momentum() {
+        let dt = 1.0 / 60.0;
+        let mut bodies = vec![
+            Body::new(0.0, 0.0, 1.0, 0.0, 1.0),
+            Body::new(0.5, 0.0, -1.0, 0.0, 1.0),
+            Body::new(1.0, 0.0, 2.0, 0.0, 1.0),
+        ];
+        for body in &mut bodies {
+            body.update(dt);
+        }
+
+        let total_mass_before: f64 = bodies.iter().map(Body::mass).sum();
+        let momentum_before: nalgebra::Vector2<f64> = bodies
+            .iter()
+            .map(|b| b.velocity(dt) * b.mass())
+            .sum();
+
+        merge_colliding_bodies(&mut bodies, dt);
+
+        assert_eq!(bodies.len(), 1, "all three bodies should have merged into one");
+
+        let total_mass_after: f64 = bodies.iter().map(Body::mass).sum();
+        let momentum_after: nalgebra::Vector2<f64> = bodies
+            .iter()
+            .map(|b| b.velocity(dt) * b.mass())
+            .sum();
+
+        assert!((total_mass_after - total_mass_before).abs() < 1e-9);
+        assert!((momentum_after - momentum_before).magnitude() < 1e-9);
+    }
+}
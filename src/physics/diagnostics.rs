@@ -0,0 +1,57 @@
+use nalgebra::{Point2, Vector2};
+use super::body::Body;
+
+/// Aggregate physical quantities for a body set, used to check that the integrator
+/// behaves: total kinetic and potential energy should stay roughly constant frame to
+/// frame, and momentum/center-of-mass drift should trend to zero for an isolated system.
+pub struct Diagnostics {
+    pub kinetic_energy: f64,
+    pub potential_energy: f64,
+    pub momentum: Vector2<f64>,
+    pub center_of_mass: Point2<f64>,
+}
+
+pub struct Simulation;
+
+impl Simulation {
+    /// Computes system diagnostics for the current body set. `g` and `eps` should
+    /// match whatever gravitational constant and softening length were used to
+    /// compute forces this frame, so potential energy lines up with kinetic energy.
+    pub fn diagnostics(bodies: &[Body], dt: f64, g: f64, eps: f64) -> Diagnostics {
+        let mut kinetic_energy = 0.0;
+        let mut momentum = Vector2::new(0.0, 0.0);
+        let mut total_mass = 0.0;
+        let mut weighted_pos = Vector2::new(0.0, 0.0);
+
+        for body in bodies {
+            let velocity = body.velocity(dt);
+            let mass = body.mass();
+
+            kinetic_energy += 0.5 * mass * velocity.magnitude_squared();
+            momentum += velocity * mass;
+            total_mass += mass;
+            weighted_pos += body.pos().coords * mass;
+        }
+
+        let center_of_mass = if total_mass > 0.0 {
+            Point2::from(weighted_pos / total_mass)
+        } else {
+            Point2::new(0.0, 0.0)
+        };
+
+        let mut potential_energy = 0.0;
+        for i in 0..bodies.len() {
+            for j in (i + 1)..bodies.len() {
+                let r_squared = (bodies[j].pos() - bodies[i].pos()).magnitude_squared();
+                potential_energy -= g * bodies[i].mass() * bodies[j].mass() / (r_squared + eps).sqrt();
+            }
+        }
+
+        Diagnostics {
+            kinetic_energy,
+            potential_energy,
+            momentum,
+            center_of_mass,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod body;
+pub mod collision;
+pub mod diagnostics;